@@ -0,0 +1,230 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rand::Rng;
+
+/// Parsed form of the `auth` argument accepted by `Client.request` / `request`.
+pub enum Auth {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+    Digest { username: String, password: String },
+}
+
+/// Parse the `auth` argument into an `Auth` value.
+///
+/// Accepts a `(username, password)` tuple for Basic auth, a bare string for
+/// Bearer auth, or a `{"username", "password", "type"}` descriptor dict for
+/// Digest auth (`type` defaults to `"digest"`).
+pub fn parse(auth: &PyAny) -> PyResult<Auth> {
+    if let Ok(token) = auth.extract::<String>() {
+        return Ok(Auth::Bearer { token });
+    }
+
+    if let Ok((username, password)) = auth.extract::<(String, String)>() {
+        return Ok(Auth::Basic { username, password });
+    }
+
+    if let Ok(dict) = auth.downcast::<PyDict>() {
+        let username: String = dict
+            .get_item("username")?
+            .ok_or_else(|| PyValueError::new_err("auth descriptor requires a 'username' field"))?
+            .extract()?;
+        let password: String = dict
+            .get_item("password")?
+            .ok_or_else(|| PyValueError::new_err("auth descriptor requires a 'password' field"))?
+            .extract()?;
+        let auth_type: String = match dict.get_item("type")? {
+            Some(v) => v.extract()?,
+            None => "digest".to_string(),
+        };
+
+        return match auth_type.as_str() {
+            "digest" => Ok(Auth::Digest { username, password }),
+            "basic" => Ok(Auth::Basic { username, password }),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown auth type: {}",
+                other
+            ))),
+        };
+    }
+
+    Err(PyValueError::new_err(
+        "auth must be a (username, password) tuple, a bearer token string, or an auth descriptor dict",
+    ))
+}
+
+/// Build a `Basic` `Authorization` header value.
+pub fn basic_header(username: &str, password: &str) -> String {
+    use base64::Engine;
+    let credentials = format!("{}:{}", username, password);
+    format!(
+        "Basic {}",
+        base64::engine::general_purpose::STANDARD.encode(credentials)
+    )
+}
+
+/// Build a `Bearer` `Authorization` header value.
+pub fn bearer_header(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+/// A parsed `WWW-Authenticate: Digest ...` challenge.
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub qop: Option<String>,
+    pub opaque: Option<String>,
+}
+
+/// Parse a `WWW-Authenticate` header value into a `DigestChallenge`.
+///
+/// Returns `None` if the header isn't a `Digest` challenge or is missing the
+/// fields required to build a response (`realm`, `nonce`).
+pub fn parse_digest_challenge(header_value: &str) -> Option<DigestChallenge> {
+    let trimmed = header_value.trim();
+    let scheme_len = trimmed.split_whitespace().next()?.len();
+    if !trimmed[..scheme_len].eq_ignore_ascii_case("digest") {
+        return None;
+    }
+    let rest = trimmed[scheme_len..].trim();
+
+    let mut realm = None;
+    let mut nonce = None;
+    let mut qop = None;
+    let mut opaque = None;
+
+    for part in split_challenge_params(rest) {
+        let (key, value) = part.split_once('=')?;
+        let key = key.trim();
+        let value = value.trim().trim_matches('"');
+        match key {
+            "realm" => realm = Some(value.to_string()),
+            "nonce" => nonce = Some(value.to_string()),
+            "qop" => qop = Some(value.to_string()),
+            "opaque" => opaque = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Some(DigestChallenge {
+        realm: realm?,
+        nonce: nonce?,
+        qop,
+        opaque,
+    })
+}
+
+/// Split a comma-separated list of `key=value` challenge params, respecting
+/// commas embedded in quoted values (e.g. a `qop` list like `"auth,auth-int"`).
+fn split_challenge_params(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(input[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(input[start..].trim());
+    parts
+}
+
+/// Generate a random client nonce (`cnonce`) for a Digest response.
+pub fn generate_cnonce() -> String {
+    let mut rng = rand::thread_rng();
+    (0..16).map(|_| format!("{:x}", rng.gen_range(0..16))).collect()
+}
+
+fn md5_hex(input: &str) -> String {
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+/// Build the `Authorization: Digest ...` header value for a request, per
+/// RFC 2617: `HA1 = MD5(username:realm:password)`, `HA2 = MD5(method:uri)`,
+/// `response = MD5(HA1:nonce:nc:cnonce:qop:HA2)`.
+pub fn digest_response_header(
+    username: &str,
+    password: &str,
+    method: &str,
+    uri: &str,
+    challenge: &DigestChallenge,
+    cnonce: &str,
+) -> String {
+    const NC: &str = "00000001";
+
+    let ha1 = md5_hex(&format!("{}:{}:{}", username, challenge.realm, password));
+    let ha2 = md5_hex(&format!("{}:{}", method, uri));
+
+    let qop_value = challenge
+        .qop
+        .as_deref()
+        .map(|qop| qop.split(',').next().unwrap_or("auth").trim());
+
+    let response = match qop_value {
+        Some(qop) => md5_hex(&format!(
+            "{}:{}:{}:{}:{}:{}",
+            ha1, challenge.nonce, NC, cnonce, qop, ha2
+        )),
+        None => md5_hex(&format!("{}:{}:{}", ha1, challenge.nonce, ha2)),
+    };
+
+    let mut header = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", response=\"{}\"",
+        username, challenge.realm, challenge.nonce, uri, response
+    );
+
+    if let Some(qop) = qop_value {
+        header.push_str(&format!(
+            ", qop={}, nc={}, cnonce=\"{}\"",
+            qop, NC, cnonce
+        ));
+    }
+
+    if let Some(opaque) = &challenge.opaque {
+        header.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Worked example from RFC 2617 section 3.5.
+    #[test]
+    fn digest_response_matches_rfc2617_example() {
+        let challenge = DigestChallenge {
+            realm: "testrealm@host.com".to_string(),
+            nonce: "dcd98b7102dd2f0e8b11d0f600bfb0c093".to_string(),
+            qop: Some("auth".to_string()),
+            opaque: Some("5ccc069c403ebaf9f0171e9517f40e41".to_string()),
+        };
+
+        let header = digest_response_header(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "/dir/index.html",
+            &challenge,
+            "0a4f113b",
+        );
+
+        assert!(header.contains("response=\"6629fae49393a05397450978507c4ef1\""));
+    }
+
+    #[test]
+    fn parse_digest_challenge_is_case_insensitive() {
+        let lower = parse_digest_challenge(r#"digest realm="r", nonce="n""#).unwrap();
+        assert_eq!(lower.realm, "r");
+        assert_eq!(lower.nonce, "n");
+
+        assert!(parse_digest_challenge(r#"Basic realm="r""#).is_none());
+    }
+}
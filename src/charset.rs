@@ -0,0 +1,68 @@
+use encoding_rs::Encoding;
+
+/// Extract the `charset` parameter from a `Content-Type` header value, e.g.
+/// `"text/html; charset=iso-8859-1"` -> `Some("iso-8859-1")`.
+pub fn from_content_type(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("charset") {
+            Some(value.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Decode `bytes` using the named charset label (e.g. `"utf-8"`,
+/// `"iso-8859-1"`, `"shift_jis"`). Falls back to UTF-8, then Latin-1
+/// (Windows-1252) if the label is unknown, absent, or decoding fails.
+pub fn decode(bytes: &[u8], label: Option<&str>) -> String {
+    if let Some(label) = label {
+        if let Some(encoding) = Encoding::for_label(label.as_bytes()) {
+            let (decoded, _, had_errors) = encoding.decode(bytes);
+            if !had_errors {
+                return decoded.into_owned();
+            }
+        }
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_content_type_extracts_charset_param() {
+        assert_eq!(
+            from_content_type("text/html; charset=iso-8859-1"),
+            Some("iso-8859-1".to_string())
+        );
+        assert_eq!(
+            from_content_type(r#"text/html; charset="utf-8""#),
+            Some("utf-8".to_string())
+        );
+        assert_eq!(from_content_type("text/html"), None);
+    }
+
+    #[test]
+    fn decode_uses_the_labeled_charset() {
+        let bytes = encoding_rs::WINDOWS_1252.encode("café").0.into_owned();
+        assert_eq!(decode(&bytes, Some("iso-8859-1")), "café");
+    }
+
+    #[test]
+    fn decode_falls_back_to_utf8_without_a_label() {
+        assert_eq!(decode("héllo".as_bytes(), None), "héllo");
+    }
+
+    #[test]
+    fn decode_falls_back_to_windows_1252_for_invalid_utf8() {
+        let bytes = encoding_rs::WINDOWS_1252.encode("café").0.into_owned();
+        assert_eq!(decode(&bytes, None), "café");
+    }
+}
@@ -0,0 +1,212 @@
+use crate::cookies;
+use once_cell::sync::Lazy;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use reqwest::blocking::Client as BlockingClient;
+use reqwest::{Certificate, Identity};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Parsed `verify` argument: a plain bool, or a path to a PEM CA bundle to
+/// trust in addition to the client's built-in root certificates.
+pub enum Verify {
+    Enabled,
+    Disabled,
+    CaBundle(String),
+}
+
+/// Parse the `verify` argument, defaulting to `Verify::Enabled`.
+pub fn parse_verify(verify: Option<&PyAny>) -> PyResult<Verify> {
+    let verify = match verify {
+        None => return Ok(Verify::Enabled),
+        Some(v) => v,
+    };
+
+    if let Ok(enabled) = verify.extract::<bool>() {
+        return Ok(if enabled { Verify::Enabled } else { Verify::Disabled });
+    }
+
+    if let Ok(path) = verify.extract::<String>() {
+        return Ok(Verify::CaBundle(path));
+    }
+
+    Err(PyValueError::new_err(
+        "verify must be a bool or a path to a PEM CA bundle",
+    ))
+}
+
+/// Parse the `proxies` argument (`{"http": "...", "https": "..."}`) into a
+/// sorted `(scheme, url)` list, sorted so it can be used as a stable cache
+/// key regardless of dict iteration order.
+pub fn parse_proxies(proxies: Option<&PyDict>) -> PyResult<Vec<(String, String)>> {
+    let mut out = Vec::new();
+    if let Some(dict) = proxies {
+        for (key, value) in dict.iter() {
+            let scheme: String = key.extract()?;
+            let url: String = value.extract()?;
+            out.push((scheme, url));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// Parse the `cert` argument for client mTLS: raw PEM bytes containing both
+/// the certificate and private key, or a path to such a PEM file.
+pub fn parse_cert(cert: Option<&PyAny>) -> PyResult<Option<Vec<u8>>> {
+    let cert = match cert {
+        None => return Ok(None),
+        Some(c) => c,
+    };
+
+    if let Ok(bytes) = cert.extract::<Vec<u8>>() {
+        return Ok(Some(bytes));
+    }
+
+    if let Ok(path) = cert.extract::<String>() {
+        let bytes = std::fs::read(&path)
+            .map_err(|e| PyValueError::new_err(format!("Failed to read cert file: {}", e)))?;
+        return Ok(Some(bytes));
+    }
+
+    Err(PyValueError::new_err(
+        "cert must be PEM bytes or a path to a PEM file",
+    ))
+}
+
+static CLIENT_CACHE: Lazy<Mutex<HashMap<String, BlockingClient>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Compute the stable cache key for a `verify`/`proxies`/`cert` combination,
+/// used both by the module-level client cache and by `Client`'s per-session
+/// custom-client cache.
+pub fn cache_key(verify: &Verify, proxies: &[(String, String)], cert: &Option<Vec<u8>>) -> String {
+    let verify_key = match verify {
+        Verify::Enabled => "1".to_string(),
+        Verify::Disabled => "0".to_string(),
+        Verify::CaBundle(path) => format!("ca:{}", path),
+    };
+    let proxies_key = proxies
+        .iter()
+        .map(|(scheme, url)| format!("{}={}", scheme, url))
+        .collect::<Vec<_>>()
+        .join(",");
+    let cert_key = match cert {
+        Some(bytes) => format!("{:x}", md5::compute(bytes)),
+        None => String::new(),
+    };
+    format!("verify={}|proxies={}|cert={}", verify_key, proxies_key, cert_key)
+}
+
+/// Build a `BlockingClient` configured for the given `verify`, `proxies`,
+/// and `cert` options.
+///
+/// Redirects are always disabled at the `reqwest` level: `rapidhttp` follows
+/// redirects itself (see `send::follow_redirects`) so it can record
+/// `Response.history` and support per-hop retries.
+pub fn build(
+    verify: &Verify,
+    proxies: &[(String, String)],
+    cert: &Option<Vec<u8>>,
+) -> PyResult<BlockingClient> {
+    build_inner(verify, proxies, cert, None)
+}
+
+/// Build a client for a `Client` session that needs a custom
+/// `verify`/`proxies`/`cert` combination, wired to that session's cookie
+/// jar. Not cached here: the module-level cache is keyed only by
+/// `verify`/`proxies`/`cert` and shared across sessions, which would leak
+/// cookies between `Client` instances that happen to share a TLS/proxy
+/// configuration. `Client` instead caches the result of this itself (keyed
+/// by `cache_key`), so the connection is still reused across calls on the
+/// same session.
+pub fn build_with_jar(
+    verify: &Verify,
+    proxies: &[(String, String)],
+    cert: &Option<Vec<u8>>,
+    jar: Arc<cookies::Jar>,
+) -> PyResult<BlockingClient> {
+    build_inner(verify, proxies, cert, Some(jar))
+}
+
+fn build_inner(
+    verify: &Verify,
+    proxies: &[(String, String)],
+    cert: &Option<Vec<u8>>,
+    jar: Option<Arc<cookies::Jar>>,
+) -> PyResult<BlockingClient> {
+    let mut builder = BlockingClient::builder()
+        .pool_max_idle_per_host(100)
+        .pool_idle_timeout(Some(Duration::from_secs(90)))
+        .timeout(Duration::from_secs(30))
+        .redirect(reqwest::redirect::Policy::none());
+
+    match verify {
+        Verify::Enabled => {}
+        Verify::Disabled => {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        Verify::CaBundle(path) => {
+            let pem = std::fs::read(path)
+                .map_err(|e| PyValueError::new_err(format!("Failed to read CA bundle: {}", e)))?;
+            let ca_cert = Certificate::from_pem(&pem)
+                .map_err(|e| PyValueError::new_err(format!("Invalid CA bundle: {}", e)))?;
+            builder = builder.add_root_certificate(ca_cert);
+        }
+    }
+
+    for (scheme, url) in proxies {
+        let proxy = match scheme.as_str() {
+            "http" => reqwest::Proxy::http(url),
+            "https" => reqwest::Proxy::https(url),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported proxy scheme: {}",
+                    other
+                )))
+            }
+        }
+        .map_err(|e| PyValueError::new_err(format!("Invalid proxy URL: {}", e)))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(pem) = cert {
+        let identity = Identity::from_pem(pem)
+            .map_err(|e| PyValueError::new_err(format!("Invalid client certificate: {}", e)))?;
+        builder = builder.identity(identity);
+    }
+
+    if let Some(jar) = jar {
+        builder = builder.cookie_provider(jar);
+    }
+
+    builder
+        .build()
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to create client: {}", e)))
+}
+
+/// Get a cached client for the given `verify`/`proxies`/`cert` combination,
+/// building and caching one if none exists yet, so repeated requests with
+/// the same configuration reuse a connection pool instead of rebuilding a
+/// client from scratch.
+pub fn get_or_build(
+    verify: &Verify,
+    proxies: &[(String, String)],
+    cert: &Option<Vec<u8>>,
+) -> PyResult<BlockingClient> {
+    let key = cache_key(verify, proxies, cert);
+
+    if let Some(client) = CLIENT_CACHE.lock().unwrap().get(&key) {
+        return Ok(client.clone());
+    }
+
+    let client = build(verify, proxies, cert)?;
+    CLIENT_CACHE
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert_with(|| client.clone());
+    Ok(client)
+}
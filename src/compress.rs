@@ -0,0 +1,63 @@
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::io::Read;
+
+fn content_encoding(headers: &HashMap<String, String>) -> Option<String> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+        .map(|(_, v)| v.to_lowercase())
+}
+
+/// Decode a response body per its `Content-Encoding` header (`gzip`,
+/// `deflate`, or `br`). Returns the original bytes unchanged if there is no
+/// recognized encoding, or if decoding fails (e.g. the body was already
+/// decoded by the transport).
+pub fn decode_body(headers: &HashMap<String, String>, bytes: Bytes) -> Bytes {
+    let decoded = match content_encoding(headers).as_deref() {
+        Some("gzip") => decode_gzip(&bytes),
+        Some("deflate") => decode_deflate(&bytes),
+        Some("br") => decode_brotli(&bytes),
+        _ => None,
+    };
+
+    decoded.unwrap_or(bytes)
+}
+
+/// Wrap a streamed body reader so it transparently decodes per its
+/// `Content-Encoding` header, matching `decode_body`'s behavior for the
+/// eager (non-streaming) path. Returns `reader` unwrapped if there's no
+/// recognized encoding.
+pub fn wrap_reader(
+    headers: &HashMap<String, String>,
+    reader: Box<dyn Read + Send>,
+) -> Box<dyn Read + Send> {
+    match content_encoding(headers).as_deref() {
+        Some("gzip") => Box::new(flate2::read::GzDecoder::new(reader)),
+        Some("deflate") => Box::new(flate2::read::DeflateDecoder::new(reader)),
+        Some("br") => Box::new(brotli::Decompressor::new(reader, 4096)),
+        _ => reader,
+    }
+}
+
+fn decode_gzip(bytes: &[u8]) -> Option<Bytes> {
+    let mut decoder = flate2::read::GzDecoder::new(bytes);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out).ok()?;
+    Some(Bytes::from(out))
+}
+
+fn decode_deflate(bytes: &[u8]) -> Option<Bytes> {
+    let mut decoder = flate2::read::DeflateDecoder::new(bytes);
+    let mut out = Vec::new();
+    decoder.read_to_end(&mut out).ok()?;
+    Some(Bytes::from(out))
+}
+
+fn decode_brotli(bytes: &[u8]) -> Option<Bytes> {
+    let mut out = Vec::new();
+    brotli::Decompressor::new(bytes, 4096)
+        .read_to_end(&mut out)
+        .ok()?;
+    Some(Bytes::from(out))
+}
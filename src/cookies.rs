@@ -0,0 +1,75 @@
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A simple name/value cookie jar backing `Client` session semantics.
+///
+/// Cookies are scoped by host only (no domain/path/expiry matching, no
+/// subdomain sharing): a `Set-Cookie` seen from `a.example.com` is never sent
+/// to `b.example.com`, but nothing finer-grained than "request host" is
+/// modeled. That's adequate for the common case this exists for
+/// (authenticate once, stay logged in against a single host) without pulling
+/// in a full RFC 6265 cookie store.
+///
+/// Cookies seeded directly via `Client.cookies` (rather than a `Set-Cookie`
+/// response header) have no request to derive a host from, so they're kept
+/// separate and sent on every host the session talks to.
+#[derive(Default)]
+pub struct Jar {
+    by_host: Mutex<HashMap<String, HashMap<String, String>>>,
+    global: Mutex<HashMap<String, String>>,
+}
+
+impl Jar {
+    /// Snapshot the jar's current contents as a name/value map, flattened
+    /// across hosts.
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut out = self.global.lock().unwrap().clone();
+        for host_cookies in self.by_host.lock().unwrap().values() {
+            out.extend(host_cookies.clone());
+        }
+        out
+    }
+
+    /// Seed a single cookie to send on every host, as if set directly rather
+    /// than via a `Set-Cookie` header.
+    pub fn set(&self, name: String, value: String) {
+        self.global.lock().unwrap().insert(name, value);
+    }
+}
+
+impl CookieStore for Jar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let Some(host) = url.host_str() else { return };
+        let mut store = self.by_host.lock().unwrap();
+        let host_cookies = store.entry(host.to_string()).or_default();
+        for header in cookie_headers {
+            let Ok(header) = header.to_str() else { continue };
+            let Some(pair) = header.split(';').next() else { continue };
+            if let Some((name, value)) = pair.split_once('=') {
+                host_cookies.insert(name.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let mut combined = self.global.lock().unwrap().clone();
+        if let Some(host) = url.host_str() {
+            if let Some(host_cookies) = self.by_host.lock().unwrap().get(host) {
+                combined.extend(host_cookies.clone());
+            }
+        }
+
+        if combined.is_empty() {
+            return None;
+        }
+        let value = combined
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&value).ok()
+    }
+}
@@ -1,11 +1,26 @@
+// pyo3 0.20's `#[pymethods]` expansion trips the `non_local_definitions`
+// lint on current rustc; harmless, and fixed by upgrading pyo3.
+#![allow(non_local_definitions)]
+
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyDict};
-use pyo3::exceptions::{PyException, PyValueError, PyRuntimeError};
+use pyo3::exceptions::{PyException, PyRuntimeError};
 use reqwest::blocking::Client as BlockingClient;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use bytes::Bytes;
 
+mod auth;
+mod charset;
+mod client_cache;
+mod compress;
+mod cookies;
+mod multipart;
+mod request_spec;
+mod retry;
+mod send;
+
 // Custom exception types
 pyo3::create_exception!(rapidhttp, HTTPError, PyException);
 pyo3::create_exception!(rapidhttp, ConnectionError, PyException);
@@ -17,41 +32,147 @@ pyo3::create_exception!(rapidhttp, RequestException, PyException);
 pyo3::create_exception!(rapidhttp, URLRequired, PyException);
 pyo3::create_exception!(rapidhttp, JSONDecodeError, PyException);
 
-/// Create a client with redirect policy
-fn create_client_with_redirects(allow_redirects: bool) -> PyResult<BlockingClient> {
-    let mut builder = BlockingClient::builder()
-        .pool_max_idle_per_host(100)
-        .pool_idle_timeout(Some(Duration::from_secs(90)))
-        .timeout(Duration::from_secs(30));
-    
-    if !allow_redirects {
-        builder = builder.redirect(reqwest::redirect::Policy::none());
+/// Map a `reqwest::Error` to the matching `rapidhttp` exception type.
+fn map_send_error(e: reqwest::Error) -> PyErr {
+    if e.is_timeout() {
+        Timeout::new_err(format!("Request timed out: {}", e))
+    } else if e.is_connect() {
+        ConnectTimeout::new_err(format!("Connection timeout: {}", e))
+    } else if e.is_redirect() {
+        TooManyRedirects::new_err(format!("Too many redirects: {}", e))
+    } else {
+        ConnectionError::new_err(format!("Connection error: {}", e))
     }
-    
-    builder.build()
-        .map_err(|e| PyRuntimeError::new_err(format!("Failed to create client: {}", e)))
+}
+
+/// Turn a final `reqwest` response into a `rapidhttp::Response`, eagerly
+/// buffering and decoding the body unless `stream` keeps it live.
+fn build_response(
+    response: reqwest::blocking::Response,
+    stream: bool,
+    history: Vec<Py<Response>>,
+) -> PyResult<Response> {
+    let status_code = response.status().as_u16();
+    let url = response.url().to_string();
+    let mut headers = HashMap::new();
+
+    for (name, value) in response.headers().iter() {
+        headers.insert(
+            name.as_str().to_string(),
+            value.to_str().unwrap_or("").to_string(),
+        );
+    }
+
+    let encoding = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .and_then(|(_, v)| charset::from_content_type(v));
+
+    let (body, raw) = if stream {
+        (None, Some(response))
+    } else {
+        (
+            response.bytes().ok().map(|b| compress::decode_body(&headers, b)),
+            None,
+        )
+    };
+
+    Ok(Response {
+        status_code,
+        url,
+        headers,
+        body,
+        raw,
+        encoding,
+        history,
+    })
 }
 
 /// Core HTTP client wrapper
 #[pyclass]
 struct Client {
     client: BlockingClient,
+    /// Session cookie jar, shared with any custom client built for a
+    /// non-default `verify`/`proxies`/`cert` combination (see `request`
+    /// below), so cookies keep flowing regardless of which client handles a
+    /// hop.
+    jar: Arc<cookies::Jar>,
+    /// Lazily-built client for a non-default `verify`/`proxies`/`cert`
+    /// combination, cached by `client_cache::cache_key` so it's rebuilt only
+    /// when those options change, not on every request.
+    custom_client: std::sync::Mutex<Option<(String, BlockingClient)>>,
+}
+
+impl Client {
+    /// Get (building and caching if needed) the client to use for a request
+    /// with a non-default `verify`/`proxies`/`cert` combination.
+    fn custom_client(
+        &self,
+        verify: &client_cache::Verify,
+        proxies: &[(String, String)],
+        cert: &Option<Vec<u8>>,
+    ) -> PyResult<BlockingClient> {
+        let key = client_cache::cache_key(verify, proxies, cert);
+
+        let mut slot = self.custom_client.lock().unwrap();
+        if let Some((cached_key, cached_client)) = slot.as_ref() {
+            if *cached_key == key {
+                return Ok(cached_client.clone());
+            }
+        }
+
+        let client = client_cache::build_with_jar(verify, proxies, cert, self.jar.clone())?;
+        *slot = Some((key, client.clone()));
+        Ok(client)
+    }
 }
 
 #[pymethods]
 impl Client {
     #[new]
     fn new() -> PyResult<Self> {
+        // Redirects are disabled here and followed manually by
+        // `send::follow_redirects`, which records `Response.history`.
+        let jar = Arc::new(cookies::Jar::default());
         let client = BlockingClient::builder()
             .pool_max_idle_per_host(100)
             .pool_idle_timeout(Some(Duration::from_secs(90)))
             .timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .cookie_provider(jar.clone())
             .build()
             .map_err(|e| PyRuntimeError::new_err(format!("Failed to create client: {}", e)))?;
-        
-        Ok(Client { client })
+
+        Ok(Client {
+            client,
+            jar,
+            custom_client: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// The session's current cookies, as a `{name: value}` dict.
+    #[getter]
+    fn cookies(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new(py);
+        for (name, value) in self.jar.to_map() {
+            dict.set_item(name, value)?;
+        }
+        Ok(dict.into())
     }
 
+    /// Seed cookies to send on subsequent requests, e.g. to restore a
+    /// previously-saved session without repeating the login request.
+    #[setter]
+    fn set_cookies(&self, cookies: &PyDict) -> PyResult<()> {
+        for (name, value) in cookies.iter() {
+            let name: String = name.extract()?;
+            let value: String = value.to_string();
+            self.jar.set(name, value);
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn request(
         &self,
         py: Python,
@@ -61,194 +182,86 @@ impl Client {
         headers: Option<&PyDict>,
         data: Option<&PyAny>,
         json: Option<&PyAny>,
+        files: Option<&PyDict>,
         timeout: Option<f64>,
         allow_redirects: Option<bool>,
-        verify: Option<bool>,
+        verify: Option<&PyAny>,
+        auth: Option<&PyAny>,
+        stream: Option<bool>,
+        proxies: Option<&PyDict>,
+        cert: Option<&PyAny>,
+        retries: Option<&PyAny>,
     ) -> PyResult<Response> {
-        let method_str = method.to_uppercase();
-        let method = reqwest::Method::from_bytes(method_str.as_bytes())
-            .map_err(|e| PyValueError::new_err(format!("Invalid HTTP method: {}", e)))?;
-
-        let mut request_builder = self.client.request(method.clone(), &url);
-
-        // Add query parameters
-        if let Some(params_dict) = params {
-            let mut query_params = Vec::new();
-            for (key, value) in params_dict.iter() {
-                let key: String = key.extract()?;
-                let value: String = value.to_string();
-                query_params.push((key, value));
-            }
-            request_builder = request_builder.query(&query_params);
-        }
-
-        // Add headers
-        if let Some(headers_dict) = headers {
-            for (key, value) in headers_dict.iter() {
-                let key: String = key.extract()?;
-                let value: String = value.to_string();
-                request_builder = request_builder.header(&key, &value);
-            }
-        }
-
-        // Add body data
-        if let Some(json_data) = json {
-            // Try to use orjson for fast JSON encoding, fallback to standard json
-            let json_str: String = py
-                .import("orjson")
-                .and_then(|orjson| {
-                    let bytes = orjson.call_method1("dumps", (json_data,))?;
-                    let bytes_obj: &PyAny = bytes;
-                    let vec: Vec<u8> = bytes_obj.extract()?;
-                    Ok(String::from_utf8(vec).unwrap_or_default())
-                })
-                .or_else(|_| {
-                    // Fallback to standard json
-                    py.import("json")?
-                        .call_method1("dumps", (json_data,))?
-                        .extract()
-                })?;
-            
-            request_builder = request_builder
-                .header("Content-Type", "application/json")
-                .body(json_str);
-        } else if let Some(body_data) = data {
-            // Handle different data types
-            if let Ok(s) = body_data.extract::<String>() {
-                request_builder = request_builder.body(s);
-            } else if let Ok(b) = body_data.extract::<Vec<u8>>() {
-                request_builder = request_builder.body(b);
-            } else if let Ok(dict) = body_data.downcast::<PyDict>() {
-                // Form data
-                let mut form_data = Vec::new();
-                for (key, value) in dict.iter() {
-                    let key: String = key.extract()?;
-                    let value: String = value.to_string();
-                    form_data.push((key, value));
-                }
-                request_builder = request_builder.form(&form_data);
-            }
-        }
-
-        // Set timeout
-        if let Some(timeout_secs) = timeout {
-            request_builder = request_builder.timeout(Duration::from_secs_f64(timeout_secs));
-        }
-
-        // Execute request with proper redirect handling
-        // If redirects are disabled, we need to create a new client
-        let mut response = if let Some(redirects) = allow_redirects {
-            if !redirects {
-                let no_redirect_client = create_client_with_redirects(false)?;
-                let mut new_request = no_redirect_client.request(method.clone(), &url);
-                
-                // Re-apply all parameters to the new request
-                if let Some(params_dict) = params {
-                    let mut query_params = Vec::new();
-                    for (key, value) in params_dict.iter() {
-                        let key: String = key.extract()?;
-                        let value: String = value.to_string();
-                        query_params.push((key, value));
-                    }
-                    new_request = new_request.query(&query_params);
-                }
-                
-                if let Some(headers_dict) = headers {
-                    for (key, value) in headers_dict.iter() {
-                        let key: String = key.extract()?;
-                        let value: String = value.to_string();
-                        new_request = new_request.header(&key, &value);
-                    }
-                }
-                
-                if let Some(json_data) = json {
-                    let json_str: String = py
-                        .import("orjson")
-                        .and_then(|orjson| {
-                            let bytes = orjson.call_method1("dumps", (json_data,))?;
-                            let bytes_obj: &PyAny = bytes;
-                            let vec: Vec<u8> = bytes_obj.extract()?;
-                            Ok(String::from_utf8(vec).unwrap_or_default())
-                        })
-                        .or_else(|_| {
-                            py.import("json")?
-                                .call_method1("dumps", (json_data,))?
-                                .extract()
-                        })?;
-                    new_request = new_request
-                        .header("Content-Type", "application/json")
-                        .body(json_str);
-                } else if let Some(body_data) = data {
-                    if let Ok(s) = body_data.extract::<String>() {
-                        new_request = new_request.body(s);
-                    } else if let Ok(b) = body_data.extract::<Vec<u8>>() {
-                        new_request = new_request.body(b);
-                    } else if let Ok(dict) = body_data.downcast::<PyDict>() {
-                        let mut form_data = Vec::new();
-                        for (key, value) in dict.iter() {
-                            let key: String = key.extract()?;
-                            let value: String = value.to_string();
-                            form_data.push((key, value));
-                        }
-                        new_request = new_request.form(&form_data);
-                    }
-                }
-                
-                if let Some(timeout_secs) = timeout {
-                    new_request = new_request.timeout(Duration::from_secs_f64(timeout_secs));
-                }
-                
-                new_request
-            } else {
-                request_builder
-            }
+        let auth = auth.map(auth::parse).transpose()?;
+        let verify = client_cache::parse_verify(verify)?;
+        let proxies = client_cache::parse_proxies(proxies)?;
+        let cert = client_cache::parse_cert(cert)?;
+        let retry_policy = retry::parse(retries)?;
+        let needs_custom_client =
+            !matches!(verify, client_cache::Verify::Enabled) || !proxies.is_empty() || cert.is_some();
+
+        let active_client = if needs_custom_client {
+            self.custom_client(&verify, &proxies, &cert)?
         } else {
-            request_builder
+            self.client.clone()
         };
-        
-        let mut response = response
-            .send()
-            .map_err(|e| {
-                if e.is_timeout() {
-                    Timeout::new_err(format!("Request timed out: {}", e))
-                } else if e.is_connect() {
-                    ConnectTimeout::new_err(format!("Connection timeout: {}", e))
-                } else if e.is_redirect() {
-                    TooManyRedirects::new_err(format!("Too many redirects: {}", e))
-                } else {
-                    ConnectionError::new_err(format!("Connection error: {}", e))
+
+        let mut spec =
+            request_spec::from_python(py, &method, &url, params, headers, data, json, files, timeout)?;
+
+        let (response, history) = match auth.as_ref() {
+            None => send::follow_redirects(
+                py,
+                &active_client,
+                spec,
+                &retry_policy,
+                allow_redirects.unwrap_or(true),
+            )?,
+            Some(a) => match send::resolve_auth(
+                py,
+                &active_client,
+                &spec,
+                a,
+                &retry_policy,
+                allow_redirects.unwrap_or(true),
+            )? {
+                send::AuthOutcome::AlreadyResponded(response, history) => (response, history),
+                send::AuthOutcome::Header(header) => {
+                    spec.headers.push(("Authorization".to_string(), header));
+                    send::follow_redirects(
+                        py,
+                        &active_client,
+                        spec,
+                        &retry_policy,
+                        allow_redirects.unwrap_or(true),
+                    )?
                 }
-            })?;
-
-        // Extract response data
-        let status_code = response.status().as_u16();
-        let url = response.url().to_string();
-        let mut headers = HashMap::new();
-        
-        for (name, value) in response.headers().iter() {
-            let name_str = name.as_str().to_string();
-            let value_str = value.to_str().unwrap_or("").to_string();
-            headers.insert(name_str, value_str);
-        }
-        
-        let body = response.bytes().ok();
-
-        Ok(Response {
-            status_code,
-            url,
-            headers,
-            body,
-        })
+            },
+        };
+
+        build_response(response, stream.unwrap_or(false), history)
     }
 }
 
+/// Default chunk size for `Response.iter_content` when none is given.
+const DEFAULT_CHUNK_SIZE: usize = 8192;
+
 /// HTTP Response wrapper
 #[pyclass]
-struct Response {
-    status_code: u16,
-    url: String,
-    headers: HashMap<String, String>,
-    body: Option<Bytes>,
+pub(crate) struct Response {
+    pub(crate) status_code: u16,
+    pub(crate) url: String,
+    pub(crate) headers: HashMap<String, String>,
+    pub(crate) body: Option<Bytes>,
+    /// The live `reqwest` response, kept open when `stream=True` instead of
+    /// being eagerly read into `body`.
+    pub(crate) raw: Option<reqwest::blocking::Response>,
+    /// Charset used by `text()`, auto-detected from `Content-Type` and
+    /// overridable by assigning `response.encoding` before calling `text()`.
+    pub(crate) encoding: Option<String>,
+    /// The `Response` for each intermediate 3xx hop, oldest first, when
+    /// redirects were followed.
+    pub(crate) history: Vec<Py<Response>>,
 }
 
 #[pymethods]
@@ -272,15 +285,28 @@ impl Response {
         Ok(dict.into())
     }
 
+    /// Decode the body as text, using `self.encoding` if set, else the
+    /// charset from `Content-Type`, falling back to UTF-8 then Latin-1.
     fn text(&mut self) -> PyResult<String> {
         if let Some(ref body) = self.body {
-            String::from_utf8(body.to_vec())
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to decode UTF-8: {}", e)))
+            Ok(charset::decode(body, self.encoding.as_deref()))
         } else {
             Err(PyRuntimeError::new_err("Response body already consumed"))
         }
     }
 
+    /// The charset used by `text()`. Auto-detected from `Content-Type`;
+    /// assign before calling `text()` to override.
+    #[getter]
+    fn encoding(&self) -> Option<String> {
+        self.encoding.clone()
+    }
+
+    #[setter]
+    fn set_encoding(&mut self, value: String) {
+        self.encoding = Some(value);
+    }
+
     fn content(&mut self, py: Python) -> PyResult<PyObject> {
         if let Some(ref body) = self.body {
             Ok(PyBytes::new(py, body).into())
@@ -309,6 +335,13 @@ impl Response {
         }
     }
 
+    /// The `Response` for each intermediate 3xx hop, oldest first, when
+    /// redirects were followed; empty otherwise.
+    #[getter]
+    fn history(&self, py: Python) -> Vec<Py<Response>> {
+        self.history.iter().map(|r| r.clone_ref(py)).collect()
+    }
+
     fn raise_for_status(&self) -> PyResult<()> {
         if self.status_code >= 400 {
             return Err(HTTPError::new_err(format!(
@@ -318,10 +351,120 @@ impl Response {
         }
         Ok(())
     }
+
+    /// Iterate over the response body in bounded chunks, reading from the
+    /// underlying stream instead of materializing the whole body. Requires
+    /// `stream=True` on the originating request.
+    fn iter_content(&mut self, chunk_size: Option<usize>) -> PyResult<ContentIterator> {
+        let raw = self.raw.take().ok_or_else(|| {
+            PyRuntimeError::new_err(
+                "iter_content requires stream=True and can only be consumed once",
+            )
+        })?;
+        let reader = compress::wrap_reader(&self.headers, Box::new(raw));
+        Ok(ContentIterator {
+            reader: Some(reader),
+            chunk_size: chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1),
+        })
+    }
+
+    /// Iterate over the response body line by line, reading from the
+    /// underlying stream instead of materializing the whole body. Requires
+    /// `stream=True` on the originating request.
+    fn iter_lines(&mut self) -> PyResult<LinesIterator> {
+        let raw = self.raw.take().ok_or_else(|| {
+            PyRuntimeError::new_err(
+                "iter_lines requires stream=True and can only be consumed once",
+            )
+        })?;
+        let reader = compress::wrap_reader(&self.headers, Box::new(raw));
+        Ok(LinesIterator {
+            reader: Some(std::io::BufReader::new(reader)),
+        })
+    }
+}
+
+/// Python iterator returned by `Response.iter_content`, yielding `bytes`
+/// chunks read directly from the underlying stream, transparently decoded
+/// per `Content-Encoding` same as the eager (non-streaming) path.
+#[pyclass]
+struct ContentIterator {
+    reader: Option<Box<dyn std::io::Read + Send>>,
+    chunk_size: usize,
+}
+
+#[pymethods]
+impl ContentIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        use std::io::Read;
+
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+
+        let mut buf = vec![0u8; self.chunk_size];
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| PyRuntimeError::new_err(format!("Error reading stream: {}", e)))?;
+
+        if n == 0 {
+            self.reader = None;
+            return Ok(None);
+        }
+
+        buf.truncate(n);
+        Ok(Some(PyBytes::new(py, &buf).into()))
+    }
+}
+
+/// Python iterator returned by `Response.iter_lines`, yielding `str` lines
+/// read directly from the underlying stream, transparently decoded per
+/// `Content-Encoding` same as the eager (non-streaming) path.
+#[pyclass]
+struct LinesIterator {
+    reader: Option<std::io::BufReader<Box<dyn std::io::Read + Send>>>,
+}
+
+#[pymethods]
+impl LinesIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PyResult<Option<String>> {
+        use std::io::BufRead;
+
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+
+        let mut line = Vec::new();
+        let n = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| PyRuntimeError::new_err(format!("Error reading stream: {}", e)))?;
+
+        if n == 0 {
+            self.reader = None;
+            return Ok(None);
+        }
+
+        while matches!(line.last(), Some(b'\n') | Some(b'\r')) {
+            line.pop();
+        }
+
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
 }
 
 /// Fast request function using connection pooling
 #[pyfunction]
+#[allow(clippy::too_many_arguments)]
 fn request(
     py: Python,
     method: String,
@@ -330,174 +473,77 @@ fn request(
     headers: Option<&PyDict>,
     data: Option<&PyAny>,
     json: Option<&PyAny>,
+    files: Option<&PyDict>,
     timeout: Option<f64>,
     allow_redirects: Option<bool>,
-    verify: Option<bool>,
+    verify: Option<&PyAny>,
+    auth: Option<&PyAny>,
+    stream: Option<bool>,
+    proxies: Option<&PyDict>,
+    cert: Option<&PyAny>,
+    retries: Option<&PyAny>,
 ) -> PyResult<Response> {
-    // Use a global client pool for automatic connection reuse
+    let auth = auth.map(auth::parse).transpose()?;
+    let verify = client_cache::parse_verify(verify)?;
+    let proxies = client_cache::parse_proxies(proxies)?;
+    let cert = client_cache::parse_cert(cert)?;
+    let retry_policy = retry::parse(retries)?;
+    let needs_custom_client =
+        !matches!(verify, client_cache::Verify::Enabled) || !proxies.is_empty() || cert.is_some();
+
+    // Use a global client pool for automatic connection reuse. Redirects are
+    // disabled here and followed manually by `send::follow_redirects`.
     use once_cell::sync::Lazy;
     static CLIENT: Lazy<BlockingClient> = Lazy::new(|| {
         BlockingClient::builder()
             .pool_max_idle_per_host(100)
             .pool_idle_timeout(Some(Duration::from_secs(90)))
             .timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
             .build()
             .expect("Failed to create HTTP client")
     });
 
-    let method_enum = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
-        .map_err(|e| PyValueError::new_err(format!("Invalid HTTP method: {}", e)))?;
-
-    let mut request_builder = CLIENT.request(method_enum.clone(), &url);
-
-    // Add query parameters
-    if let Some(params_dict) = params {
-        let mut query_params = Vec::new();
-        for (key, value) in params_dict.iter() {
-            let key: String = key.extract()?;
-            let value: String = value.to_string();
-            query_params.push((key, value));
-        }
-        request_builder = request_builder.query(&query_params);
-    }
-
-    // Add headers
-    if let Some(headers_dict) = headers {
-        for (key, value) in headers_dict.iter() {
-            let key: String = key.extract()?;
-            let value: String = value.to_string();
-            request_builder = request_builder.header(&key, &value);
-        }
-    }
-
-    // Add body data
-    if let Some(json_data) = json {
-        let json_str: String = py.import("json")?.call_method1("dumps", (json_data,))?.extract()?;
-        request_builder = request_builder
-            .header("Content-Type", "application/json")
-            .body(json_str);
-    } else if let Some(body_data) = data {
-        if let Ok(s) = body_data.extract::<String>() {
-            request_builder = request_builder.body(s);
-        } else if let Ok(b) = body_data.extract::<Vec<u8>>() {
-            request_builder = request_builder.body(b);
-        } else if let Ok(dict) = body_data.downcast::<PyDict>() {
-            let mut form_data = Vec::new();
-            for (key, value) in dict.iter() {
-                let key: String = key.extract()?;
-                let value: String = value.to_string();
-                form_data.push((key, value));
-            }
-            request_builder = request_builder.form(&form_data);
-        }
-    }
-
-    // Set timeout
-    if let Some(timeout_secs) = timeout {
-        request_builder = request_builder.timeout(Duration::from_secs_f64(timeout_secs));
-    }
-
-    // Execute request with proper redirect handling
-    let mut response = if let Some(redirects) = allow_redirects {
-        if !redirects {
-            let no_redirect_client = create_client_with_redirects(false)?;
-            let mut new_request = no_redirect_client.request(method_enum.clone(), &url);
-            
-            // Re-apply all parameters
-            if let Some(params_dict) = params {
-                let mut query_params = Vec::new();
-                for (key, value) in params_dict.iter() {
-                    let key: String = key.extract()?;
-                    let value: String = value.to_string();
-                    query_params.push((key, value));
-                }
-                new_request = new_request.query(&query_params);
-            }
-            
-            if let Some(headers_dict) = headers {
-                for (key, value) in headers_dict.iter() {
-                    let key: String = key.extract()?;
-                    let value: String = value.to_string();
-                    new_request = new_request.header(&key, &value);
-                }
-            }
-            
-            if let Some(json_data) = json {
-                let json_str: String = py
-                    .import("orjson")
-                    .and_then(|orjson| {
-                        let bytes = orjson.call_method1("dumps", (json_data,))?;
-                        let bytes_obj: &PyAny = bytes;
-                        let vec: Vec<u8> = bytes_obj.extract()?;
-                        Ok(String::from_utf8(vec).unwrap_or_default())
-                    })
-                    .or_else(|_| {
-                        py.import("json")?
-                            .call_method1("dumps", (json_data,))?
-                            .extract()
-                    })?;
-                new_request = new_request
-                    .header("Content-Type", "application/json")
-                    .body(json_str);
-            } else if let Some(body_data) = data {
-                if let Ok(s) = body_data.extract::<String>() {
-                    new_request = new_request.body(s);
-                } else if let Ok(b) = body_data.extract::<Vec<u8>>() {
-                    new_request = new_request.body(b);
-                } else if let Ok(dict) = body_data.downcast::<PyDict>() {
-                    let mut form_data = Vec::new();
-                    for (key, value) in dict.iter() {
-                        let key: String = key.extract()?;
-                        let value: String = value.to_string();
-                        form_data.push((key, value));
-                    }
-                    new_request = new_request.form(&form_data);
-                }
-            }
-            
-            if let Some(timeout_secs) = timeout {
-                new_request = new_request.timeout(Duration::from_secs_f64(timeout_secs));
-            }
-            
-            new_request
-        } else {
-            request_builder
-        }
+    let active_client = if needs_custom_client {
+        client_cache::get_or_build(&verify, &proxies, &cert)?
     } else {
-        request_builder
+        CLIENT.clone()
     };
-    
-    let response = response.send().map_err(|e| {
-        if e.is_timeout() {
-            Timeout::new_err(format!("Request timed out: {}", e))
-        } else if e.is_connect() {
-            ConnectTimeout::new_err(format!("Connection timeout: {}", e))
-        } else if e.is_redirect() {
-            TooManyRedirects::new_err(format!("Too many redirects: {}", e))
-        } else {
-            ConnectionError::new_err(format!("Connection error: {}", e))
-        }
-    })?;
 
-    // Extract response data
-    let status_code = response.status().as_u16();
-    let url = response.url().to_string();
-    let mut headers = HashMap::new();
-    
-    for (name, value) in response.headers().iter() {
-        let name_str = name.as_str().to_string();
-        let value_str = value.to_str().unwrap_or("").to_string();
-        headers.insert(name_str, value_str);
-    }
-    
-    let body = response.bytes().ok();
+    let mut spec =
+        request_spec::from_python(py, &method, &url, params, headers, data, json, files, timeout)?;
+
+    let (response, history) = match auth.as_ref() {
+        None => send::follow_redirects(
+            py,
+            &active_client,
+            spec,
+            &retry_policy,
+            allow_redirects.unwrap_or(true),
+        )?,
+        Some(a) => match send::resolve_auth(
+            py,
+            &active_client,
+            &spec,
+            a,
+            &retry_policy,
+            allow_redirects.unwrap_or(true),
+        )? {
+            send::AuthOutcome::AlreadyResponded(response, history) => (response, history),
+            send::AuthOutcome::Header(header) => {
+                spec.headers.push(("Authorization".to_string(), header));
+                send::follow_redirects(
+                    py,
+                    &active_client,
+                    spec,
+                    &retry_policy,
+                    allow_redirects.unwrap_or(true),
+                )?
+            }
+        },
+    };
 
-    Ok(Response {
-        status_code,
-        url,
-        headers,
-        body,
-    })
+    build_response(response, stream.unwrap_or(false), history)
 }
 
 /// Python module definition
@@ -505,6 +551,8 @@ fn request(
 fn _rapidhttp(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<Client>()?;
     m.add_class::<Response>()?;
+    m.add_class::<ContentIterator>()?;
+    m.add_class::<LinesIterator>()?;
     m.add_function(wrap_pyfunction!(request, m)?)?;
     
     // Add exception types
@@ -0,0 +1,69 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use reqwest::blocking::multipart::{Form, Part};
+
+/// One entry of the `files` dict, parsed into owned Rust data so it can be
+/// replayed (e.g. across retries) without touching Python objects again.
+#[derive(Clone)]
+pub enum FileField {
+    Bytes(Vec<u8>),
+    Named(String, Vec<u8>),
+    NamedTyped(String, Vec<u8>, String),
+}
+
+/// Parse the `files` dict into owned `FileField`s, keyed by form field name.
+pub fn parse_files(files: &PyDict) -> PyResult<Vec<(String, FileField)>> {
+    let mut out = Vec::new();
+    for (key, value) in files.iter() {
+        let field: String = key.extract()?;
+        out.push((field, parse_file_field(value)?));
+    }
+    Ok(out)
+}
+
+fn parse_file_field(value: &PyAny) -> PyResult<FileField> {
+    if let Ok((filename, bytes, content_type)) = value.extract::<(String, Vec<u8>, String)>() {
+        return Ok(FileField::NamedTyped(filename, bytes, content_type));
+    }
+
+    if let Ok((filename, bytes)) = value.extract::<(String, Vec<u8>)>() {
+        return Ok(FileField::Named(filename, bytes));
+    }
+
+    if let Ok(bytes) = value.extract::<Vec<u8>>() {
+        return Ok(FileField::Bytes(bytes));
+    }
+
+    Err(PyValueError::new_err(
+        "files values must be bytes, a (filename, bytes) tuple, or a (filename, bytes, content_type) triple",
+    ))
+}
+
+/// Build a fresh `multipart::Form` from parsed file fields, merging in any
+/// scalar `data` fields as text parts.
+pub fn build_form(files: &[(String, FileField)], data: &[(String, String)]) -> PyResult<Form> {
+    let mut form = Form::new();
+
+    for (field, value) in files {
+        let part = match value {
+            FileField::Bytes(bytes) => Part::bytes(bytes.clone()),
+            FileField::Named(filename, bytes) => {
+                Part::bytes(bytes.clone()).file_name(filename.clone())
+            }
+            FileField::NamedTyped(filename, bytes, content_type) => {
+                Part::bytes(bytes.clone())
+                    .file_name(filename.clone())
+                    .mime_str(content_type)
+                    .map_err(|e| PyValueError::new_err(format!("Invalid content type: {}", e)))?
+            }
+        };
+        form = form.part(field.clone(), part);
+    }
+
+    for (key, value) in data {
+        form = form.text(key.clone(), value.clone());
+    }
+
+    Ok(form)
+}
@@ -0,0 +1,193 @@
+use crate::multipart::{self, FileField};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::time::Duration;
+
+/// Body ingredients for a request. Kept as owned data (rather than a built
+/// `reqwest` body) so a `RequestSpec` can be rebuilt and resent for retries
+/// and redirect replays without re-touching Python objects or a consumed
+/// request body.
+#[derive(Clone)]
+pub enum Body {
+    None,
+    Json(String),
+    Text(String),
+    Bytes(Vec<u8>),
+    Form(Vec<(String, String)>),
+    Multipart {
+        files: Vec<(String, FileField)>,
+        data: Vec<(String, String)>,
+    },
+}
+
+/// A frozen request: every ingredient needed to build a
+/// `reqwest::blocking::RequestBuilder`, gathered once so it can be rebuilt
+/// from scratch for each retry attempt or redirect hop.
+#[derive(Clone)]
+pub struct RequestSpec {
+    pub method: reqwest::Method,
+    pub url: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Body,
+    pub timeout: Option<Duration>,
+}
+
+impl RequestSpec {
+    /// Build a fresh `RequestBuilder` against `client` from this spec.
+    pub fn build(&self, client: &reqwest::blocking::Client) -> PyResult<reqwest::blocking::RequestBuilder> {
+        let mut builder = client.request(self.method.clone(), &self.url);
+
+        if !self.query.is_empty() {
+            builder = builder.query(&self.query);
+        }
+
+        for (key, value) in &self.headers {
+            builder = builder.header(key, value);
+        }
+
+        builder = match &self.body {
+            Body::None => builder,
+            Body::Json(s) => builder
+                .header("Content-Type", "application/json")
+                .body(s.clone()),
+            Body::Text(s) => builder.body(s.clone()),
+            Body::Bytes(b) => builder.body(b.clone()),
+            Body::Form(fields) => builder.form(fields),
+            Body::Multipart { files, data } => builder.multipart(multipart::build_form(files, data)?),
+        };
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(builder)
+    }
+
+    /// Build the spec to replay after following a redirect to `next_url`.
+    /// Per RFC 7231, 301/302/303 responses to non-GET/HEAD requests are
+    /// replayed as a bodyless GET; 307/308 preserve the method and body.
+    pub fn for_redirect(&self, status: u16, next_url: String) -> RequestSpec {
+        let mut method = self.method.clone();
+        let mut body = match &self.body {
+            Body::None => Body::None,
+            Body::Json(s) => Body::Json(s.clone()),
+            Body::Text(s) => Body::Text(s.clone()),
+            Body::Bytes(b) => Body::Bytes(b.clone()),
+            Body::Form(f) => Body::Form(f.clone()),
+            Body::Multipart { files, data } => Body::Multipart {
+                files: files.clone(),
+                data: data.clone(),
+            },
+        };
+
+        let rewrite_to_get = matches!(status, 301..=303)
+            && !matches!(method, reqwest::Method::GET | reqwest::Method::HEAD);
+        if rewrite_to_get {
+            method = reqwest::Method::GET;
+            body = Body::None;
+        }
+
+        RequestSpec {
+            method,
+            url: next_url,
+            query: Vec::new(),
+            headers: self.headers.clone(),
+            body,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// Encode a JSON-able Python object to a string, preferring `orjson` and
+/// falling back to the standard library `json` module.
+fn encode_json(py: Python, json_data: &PyAny) -> PyResult<String> {
+    py.import("orjson")
+        .and_then(|orjson| {
+            let bytes = orjson.call_method1("dumps", (json_data,))?;
+            let bytes_obj: &PyAny = bytes;
+            let vec: Vec<u8> = bytes_obj.extract()?;
+            Ok(String::from_utf8(vec).unwrap_or_default())
+        })
+        .or_else(|_: PyErr| {
+            py.import("json")?
+                .call_method1("dumps", (json_data,))?
+                .extract()
+        })
+}
+
+/// Gather the `method`/`url`/`params`/`headers`/`data`/`json`/`files`/
+/// `timeout` arguments into a `RequestSpec`, ready to be built and replayed.
+#[allow(clippy::too_many_arguments)]
+pub fn from_python(
+    py: Python,
+    method: &str,
+    url: &str,
+    params: Option<&PyDict>,
+    headers: Option<&PyDict>,
+    data: Option<&PyAny>,
+    json: Option<&PyAny>,
+    files: Option<&PyDict>,
+    timeout: Option<f64>,
+) -> PyResult<RequestSpec> {
+    use pyo3::exceptions::PyValueError;
+
+    let method_enum = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|e| PyValueError::new_err(format!("Invalid HTTP method: {}", e)))?;
+
+    let mut query = Vec::new();
+    if let Some(params_dict) = params {
+        for (key, value) in params_dict.iter() {
+            query.push((key.extract::<String>()?, value.to_string()));
+        }
+    }
+
+    let mut header_pairs = Vec::new();
+    if let Some(headers_dict) = headers {
+        for (key, value) in headers_dict.iter() {
+            header_pairs.push((key.extract::<String>()?, value.to_string()));
+        }
+    }
+
+    let body = if let Some(files_dict) = files {
+        let parsed_files = multipart::parse_files(files_dict)?;
+        let parsed_data = match data.and_then(|d| d.downcast::<PyDict>().ok()) {
+            Some(dict) => dict
+                .iter()
+                .map(|(k, v)| Ok((k.extract::<String>()?, v.to_string())))
+                .collect::<PyResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        Body::Multipart {
+            files: parsed_files,
+            data: parsed_data,
+        }
+    } else if let Some(json_data) = json {
+        Body::Json(encode_json(py, json_data)?)
+    } else if let Some(body_data) = data {
+        if let Ok(s) = body_data.extract::<String>() {
+            Body::Text(s)
+        } else if let Ok(b) = body_data.extract::<Vec<u8>>() {
+            Body::Bytes(b)
+        } else if let Ok(dict) = body_data.downcast::<PyDict>() {
+            let mut form_data = Vec::new();
+            for (key, value) in dict.iter() {
+                form_data.push((key.extract::<String>()?, value.to_string()));
+            }
+            Body::Form(form_data)
+        } else {
+            Body::None
+        }
+    } else {
+        Body::None
+    };
+
+    Ok(RequestSpec {
+        method: method_enum,
+        url: url.to_string(),
+        query,
+        headers: header_pairs,
+        body,
+        timeout: timeout.map(Duration::from_secs_f64),
+    })
+}
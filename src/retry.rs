@@ -0,0 +1,131 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Retry behavior for a request: how many attempts to make, how long to
+/// back off between them, and which response statuses are worth retrying.
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff_factor: f64,
+    pub retry_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            backoff_factor: 0.0,
+            retry_statuses: vec![429, 502, 503, 504],
+        }
+    }
+}
+
+/// Parse the `retries` argument: either a bare int (max attempts, with the
+/// default backoff and retryable statuses), or a dict with `total`
+/// (or `max_attempts`), `backoff_factor`, and `status_forcelist` keys.
+pub fn parse(retries: Option<&PyAny>) -> PyResult<RetryPolicy> {
+    let retries = match retries {
+        None => return Ok(RetryPolicy::default()),
+        Some(r) => r,
+    };
+
+    if let Ok(n) = retries.extract::<u32>() {
+        return Ok(RetryPolicy {
+            max_attempts: n.max(1),
+            ..RetryPolicy::default()
+        });
+    }
+
+    if let Ok(dict) = retries.downcast::<PyDict>() {
+        let total = match dict.get_item("total")? {
+            Some(v) => Some(v),
+            None => dict.get_item("max_attempts")?,
+        };
+        let max_attempts = match total {
+            Some(v) => v.extract::<u32>()?,
+            None => 1,
+        }
+        .max(1);
+
+        let backoff_factor = match dict.get_item("backoff_factor")? {
+            Some(v) => v.extract::<f64>()?,
+            None => 0.0,
+        };
+
+        let retry_statuses = match dict.get_item("status_forcelist")? {
+            Some(v) => v.extract::<Vec<u16>>()?,
+            None => vec![429, 502, 503, 504],
+        };
+
+        return Ok(RetryPolicy {
+            max_attempts,
+            backoff_factor,
+            retry_statuses,
+        });
+    }
+
+    Err(PyValueError::new_err(
+        "retries must be an int (max attempts) or a dict with total/backoff_factor/status_forcelist",
+    ))
+}
+
+/// How long to wait before the next attempt: honors a `Retry-After` header
+/// (seconds, or an HTTP date) when present, else `backoff_factor * 2^(attempt-1)`.
+pub fn delay_for(
+    policy: &RetryPolicy,
+    attempt: u32,
+    retry_after: Option<&str>,
+) -> std::time::Duration {
+    if let Some(value) = retry_after {
+        if let Ok(secs) = value.trim().parse::<f64>() {
+            return std::time::Duration::from_secs_f64(secs.max(0.0));
+        }
+        if let Ok(date) = httpdate::parse_http_date(value.trim()) {
+            if let Ok(remaining) = date.duration_since(std::time::SystemTime::now()) {
+                return remaining;
+            }
+            return std::time::Duration::ZERO;
+        }
+    }
+
+    std::time::Duration::from_secs_f64(policy.backoff_factor * 2f64.powi(attempt as i32 - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(backoff_factor: f64) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            backoff_factor,
+            retry_statuses: vec![429, 502, 503, 504],
+        }
+    }
+
+    #[test]
+    fn delay_for_honors_a_numeric_retry_after() {
+        let delay = delay_for(&policy(1.0), 1, Some("2"));
+        assert_eq!(delay, std::time::Duration::from_secs(2));
+    }
+
+    #[test]
+    fn delay_for_honors_an_http_date_retry_after() {
+        let future = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        let header = httpdate::fmt_http_date(future);
+        let delay = delay_for(&policy(1.0), 1, Some(&header));
+        assert!(delay.as_secs() > 0 && delay.as_secs() <= 60);
+    }
+
+    #[test]
+    fn delay_for_falls_back_to_exponential_backoff() {
+        assert_eq!(
+            delay_for(&policy(0.5), 1, None),
+            std::time::Duration::from_secs_f64(0.5)
+        );
+        assert_eq!(
+            delay_for(&policy(0.5), 3, None),
+            std::time::Duration::from_secs_f64(2.0)
+        );
+    }
+}
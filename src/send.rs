@@ -0,0 +1,190 @@
+use crate::request_spec::RequestSpec;
+use crate::retry::{self, RetryPolicy};
+use crate::{auth, charset, compress, map_send_error, Response, TooManyRedirects};
+use pyo3::prelude::*;
+use reqwest::blocking::Client as BlockingClient;
+
+/// Resolve the `auth` argument into either a concrete `Authorization` header
+/// to attach to `spec`, or (for Digest, when the probe's response is not a 401
+/// or its 401 carries no usable Digest challenge) a response that's already
+/// final, with whatever redirect history the probe itself accumulated.
+pub enum AuthOutcome {
+    Header(String),
+    AlreadyResponded(reqwest::blocking::Response, Vec<Py<Response>>),
+}
+
+/// Apply `Basic`/`Bearer` auth directly, or drive the Digest
+/// challenge/response handshake with a probe request, per RFC 2617. The
+/// probe is sent through the same retry/redirect machinery as a normal
+/// request, so `retries` and `allow_redirects` aren't silently ignored on
+/// resources that don't challenge (or redirect before challenging).
+pub fn resolve_auth(
+    py: Python,
+    client: &BlockingClient,
+    spec: &RequestSpec,
+    auth: &auth::Auth,
+    policy: &RetryPolicy,
+    allow_redirects: bool,
+) -> PyResult<AuthOutcome> {
+    match auth {
+        auth::Auth::Basic { username, password } => {
+            Ok(AuthOutcome::Header(auth::basic_header(username, password)))
+        }
+        auth::Auth::Bearer { token } => Ok(AuthOutcome::Header(auth::bearer_header(token))),
+        auth::Auth::Digest { username, password } => {
+            let (probe, history) =
+                follow_redirects(py, client, spec.clone(), policy, allow_redirects)?;
+            if probe.status() != reqwest::StatusCode::UNAUTHORIZED {
+                return Ok(AuthOutcome::AlreadyResponded(probe, history));
+            }
+
+            let challenge = probe
+                .headers()
+                .get(reqwest::header::WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(auth::parse_digest_challenge);
+
+            let challenge = match challenge {
+                Some(c) => c,
+                None => return Ok(AuthOutcome::AlreadyResponded(probe, history)),
+            };
+
+            let uri = reqwest::Url::parse(&spec.url)
+                .map(|u| match u.query() {
+                    Some(query) => format!("{}?{}", u.path(), query),
+                    None => u.path().to_string(),
+                })
+                .unwrap_or_else(|_| spec.url.clone());
+
+            let cnonce = auth::generate_cnonce();
+            let header = auth::digest_response_header(
+                username,
+                password,
+                spec.method.as_str(),
+                &uri,
+                &challenge,
+                &cnonce,
+            );
+            Ok(AuthOutcome::Header(header))
+        }
+    }
+}
+
+/// Send `spec` against `client`, retrying per `policy` on connection errors
+/// and on retryable status codes, honoring a `Retry-After` header. The send
+/// itself and the backoff sleep both release the GIL, so a slow request or a
+/// long retry delay doesn't freeze the rest of the Python process.
+fn send_with_retries(
+    py: Python,
+    client: &BlockingClient,
+    spec: &RequestSpec,
+    policy: &RetryPolicy,
+) -> PyResult<reqwest::blocking::Response> {
+    let mut attempt = 1;
+    loop {
+        let built = spec.build(client)?;
+        match py.allow_threads(|| built.send()) {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                if attempt < policy.max_attempts && policy.retry_statuses.contains(&status) {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    let delay = retry::delay_for(policy, attempt, retry_after.as_deref());
+                    py.allow_threads(|| std::thread::sleep(delay));
+                    attempt += 1;
+                    continue;
+                }
+                return Ok(response);
+            }
+            Err(e) => {
+                if attempt < policy.max_attempts {
+                    let delay = retry::delay_for(policy, attempt, None);
+                    py.allow_threads(|| std::thread::sleep(delay));
+                    attempt += 1;
+                    continue;
+                }
+                return Err(map_send_error(e));
+            }
+        }
+    }
+}
+
+/// Read a hop's response into a buffered `rapidhttp::Response` snapshot, for
+/// `Response.history`.
+fn snapshot(response: reqwest::blocking::Response) -> PyResult<Response> {
+    let status_code = response.status().as_u16();
+    let url = response.url().to_string();
+    let mut headers = std::collections::HashMap::new();
+    for (name, value) in response.headers().iter() {
+        headers.insert(name.as_str().to_string(), value.to_str().unwrap_or("").to_string());
+    }
+    let encoding = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .and_then(|(_, v)| charset::from_content_type(v));
+    let body = response
+        .bytes()
+        .ok()
+        .map(|b| compress::decode_body(&headers, b));
+
+    Ok(Response {
+        status_code,
+        url,
+        headers,
+        body,
+        raw: None,
+        encoding,
+        history: Vec::new(),
+    })
+}
+
+const MAX_REDIRECTS: u32 = 20;
+
+/// Send a request, following 3xx redirects (when `allow_redirects`) and
+/// recording each intermediate hop into the returned history, retrying each
+/// hop per `policy`.
+pub fn follow_redirects(
+    py: Python,
+    client: &BlockingClient,
+    mut spec: RequestSpec,
+    policy: &RetryPolicy,
+    allow_redirects: bool,
+) -> PyResult<(reqwest::blocking::Response, Vec<Py<Response>>)> {
+    let mut history = Vec::new();
+
+    for _ in 0..MAX_REDIRECTS {
+        let response = send_with_retries(py, client, &spec, policy)?;
+
+        if !allow_redirects || !response.status().is_redirection() {
+            return Ok((response, history));
+        }
+
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        let location = match location {
+            Some(l) => l,
+            None => return Ok((response, history)),
+        };
+
+        let next_url = reqwest::Url::parse(&spec.url)
+            .and_then(|base| base.join(&location))
+            .map(|u| u.to_string())
+            .unwrap_or(location);
+
+        let status = response.status().as_u16();
+        history.push(Py::new(py, snapshot(response)?)?);
+        spec = spec.for_redirect(status, next_url);
+    }
+
+    Err(TooManyRedirects::new_err(format!(
+        "Exceeded {} redirects",
+        MAX_REDIRECTS
+    )))
+}